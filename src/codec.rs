@@ -0,0 +1,98 @@
+//! A small length-prefixed binary codec shared by every `to_bytes`/`from_bytes` impl in this
+//! crate. Every point is written compressed and every vector is prefixed with its length as a
+//! little-endian `u64`, so a reader can reject truncated, trailing, or oversized input before
+//! doing any group arithmetic on it.
+
+use blstrs::{G1Affine, Scalar};
+
+use crate::Error;
+
+pub(crate) struct Writer(Vec<u8>);
+
+impl Writer {
+    pub(crate) fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub(crate) fn scalar(&mut self, s: &Scalar) {
+        self.0.extend_from_slice(&s.to_bytes_le());
+    }
+
+    pub(crate) fn point(&mut self, p: &G1Affine) {
+        self.0.extend_from_slice(&p.to_compressed());
+    }
+
+    pub(crate) fn len(&mut self, n: usize) {
+        self.0.extend_from_slice(&(n as u64).to_le_bytes());
+    }
+
+    /// Writes `data` as a length-prefixed opaque byte blob (used for nested encodings, such as a
+    /// [`bulletproofs::RangeProof`] or a fully encoded sub-signature).
+    pub(crate) fn blob(&mut self, data: &[u8]) {
+        self.len(data.len());
+        self.0.extend_from_slice(data);
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> crate::Result<&'a [u8]> {
+        let end = self.pos.checked_add(n).ok_or(Error::MalformedEncoding)?;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(Error::MalformedEncoding)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn scalar(&mut self) -> crate::Result<Scalar> {
+        let bytes: [u8; 32] = self.take(32)?.try_into().expect("took exactly 32 bytes");
+        Option::from(Scalar::from_bytes_le(&bytes)).ok_or(Error::MalformedEncoding)
+    }
+
+    /// Reads a compressed G1 point, rejecting anything that is not a canonical, on-curve
+    /// encoding.
+    pub(crate) fn point(&mut self) -> crate::Result<G1Affine> {
+        let bytes: [u8; 48] = self.take(48)?.try_into().expect("took exactly 48 bytes");
+        Option::from(G1Affine::from_compressed(&bytes)).ok_or(Error::InvalidPoint)
+    }
+
+    /// Reads a length prefix, rejecting one that exceeds `max_len` so a malicious length can't
+    /// drive an oversized allocation before its contents are even read.
+    pub(crate) fn len_prefix(&mut self, max_len: usize) -> crate::Result<usize> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().expect("took exactly 8 bytes");
+        let n = u64::from_le_bytes(bytes) as usize;
+        if n > max_len {
+            return Err(Error::MalformedEncoding);
+        }
+        Ok(n)
+    }
+
+    /// Reads a length-prefixed opaque byte blob written by [`Writer::blob`].
+    pub(crate) fn blob(&mut self, max_len: usize) -> crate::Result<&'a [u8]> {
+        let n = self.len_prefix(max_len)?;
+        self.take(n)
+    }
+
+    /// Fails if any bytes remain unconsumed.
+    pub(crate) fn finish(self) -> crate::Result<()> {
+        if self.pos == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(Error::MalformedEncoding)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+
+/// Errors that can occur when building, signing or verifying RingCT transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Two dimensions of a decoded signature that must agree do not: the number of response
+    /// scalars and ring members of a CLSAG, or the number of CLSAGs and pseudo commitments.
+    RingSizeMismatch { expected: usize, got: usize },
+    /// A signature (or one of its component ring signatures) failed to verify.
+    VerificationFailed,
+    /// A serialized point was not a canonical, on-curve encoding.
+    InvalidPoint,
+    /// A serialized blob was truncated, had trailing bytes, or claimed an oversized length
+    /// prefix.
+    MalformedEncoding,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RingSizeMismatch { expected, got } => write!(
+                f,
+                "ring size mismatch: expected {expected}, got {got}"
+            ),
+            Self::VerificationFailed => write!(f, "signature verification failed"),
+            Self::InvalidPoint => write!(f, "serialized point is not canonical or not on curve"),
+            Self::MalformedEncoding => write!(f, "malformed or oversized serialized encoding"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
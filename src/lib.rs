@@ -1,8 +1,22 @@
+//! Note on building this crate: `bulletproofs::PedersenGens`/`RangeProof` are used here over
+//! `blstrs::G1Projective` commitments (see `pedersen_gens`), which the published `bulletproofs`
+//! crate on crates.io does not support (it is hard-coded to `curve25519-dalek`). This tree
+//! intentionally ships no `Cargo.toml`; building it for real requires pinning a `bulletproofs`
+//! fork generic over (or ported to) `blstrs`'s group types, which is out of scope for this change
+//! and has not been pinned or verified here.
+
+mod codec;
 pub mod error;
 pub mod mlsag;
+#[cfg(feature = "multisig")]
+pub mod multisig;
 pub mod ringct;
+mod transcript;
 
-use blstrs::{group::ff::Field, G1Projective, Scalar};
+use blstrs::{
+    group::{ff::Field, Group},
+    G1Projective, Scalar,
+};
 
 pub use blstrs;
 pub use error::Error;
@@ -11,6 +25,18 @@ pub use ringct::{Output, RingCtMaterial};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The crate's fixed Pedersen commitment generators. `B_blinding` is deliberately the same
+/// generator `G` used for public keys and key images throughout [`mlsag`], rather than
+/// `bulletproofs::PedersenGens::default()`'s unrelated hash-derived point, so a commitment-to-zero
+/// `value * B + blinding * G` can be opened as `blinding * G` in the CLSAG ring equations.
+pub(crate) fn pedersen_gens() -> bulletproofs::PedersenGens {
+    const DOMAIN: &[u8; 38] = b"blst-ringct-pedersen-value-generator-B";
+    bulletproofs::PedersenGens {
+        B: G1Projective::hash_to_curve(b"sn_ringct-pedersen-value-generator", DOMAIN, &[]),
+        B_blinding: G1Projective::generator(),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct RevealedCommitment {
     pub value: u64,
@@ -39,12 +65,6 @@ impl RevealedCommitment {
     }
 }
 
-/// Hashes a point to another point on the G1 curve
-pub fn hash_to_curve(p: G1Projective) -> G1Projective {
-    const DOMAIN: &[u8; 25] = b"blst-ringct-hash-to-curve";
-    G1Projective::hash_to_curve(&p.to_compressed(), DOMAIN, &[])
-}
-
 #[cfg(test)]
 mod tests {
 
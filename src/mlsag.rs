@@ -0,0 +1,527 @@
+use std::collections::HashMap;
+
+use blstrs::{
+    group::{ff::Field, Curve, Group},
+    G1Affine, G1Projective, Scalar,
+};
+use rand_core::RngCore;
+use tiny_keccak::{Hasher, Sha3};
+
+use crate::codec::{Reader, Writer};
+use crate::transcript::Transcript;
+use crate::{Error, RevealedCommitment};
+
+/// An upper bound on the number of ring members (and, equivalently, response scalars) a decoded
+/// [`MlsagSignature`] is allowed to claim, so a malicious length prefix can't drive an oversized
+/// allocation before its contents are even read.
+const MAX_RING_SIZE: usize = 1 << 16;
+
+/// Hashes a point to another point on the G1 curve, used to derive the per-key generator `Hp(P)`
+/// that key images and CLSAG nonces are built from.
+pub(crate) fn hash_to_curve(p: G1Projective) -> G1Projective {
+    const DOMAIN: &[u8; 35] = b"blst-ringct-signature-hash-to-curve";
+    G1Projective::hash_to_curve(&p.to_compressed(), DOMAIN, &[])
+}
+
+pub struct TrueInput {
+    pub secret_key: Scalar,
+    pub revealed_commitment: RevealedCommitment,
+}
+
+impl TrueInput {
+    pub fn public_key(&self) -> G1Projective {
+        G1Projective::generator() * self.secret_key
+    }
+
+    /// Computes the Key Image for this inputs keypair
+    /// A key image is defined to be I = x * Hp(P)
+    pub fn key_image(&self) -> G1Projective {
+        hash_to_curve(self.public_key()) * self.secret_key
+    }
+}
+
+pub struct DecoyInput {
+    pub public_key: G1Affine,
+    pub commitment: G1Affine,
+}
+
+impl DecoyInput {
+    fn public_key(&self) -> G1Affine {
+        self.public_key
+    }
+
+    fn commitment(&self) -> G1Affine {
+        self.commitment
+    }
+}
+
+/// The keys and decoys a single input contributes to a ring signature. A `RingCtMaterial`
+/// holds one of these per input being spent.
+pub struct MlsagMaterial {
+    pub true_input: TrueInput,
+    pub decoy_inputs: Vec<DecoyInput>,
+}
+
+impl MlsagMaterial {
+    pub fn ring_size(&self) -> usize {
+        self.decoy_inputs.len() + 1
+    }
+
+    pub(crate) fn decoys_len(&self) -> usize {
+        self.decoy_inputs.len()
+    }
+
+    pub(crate) fn public_key(&self) -> G1Affine {
+        self.true_input.public_key().to_affine()
+    }
+
+    pub(crate) fn decoy_public_key(&self, n: usize) -> G1Affine {
+        self.decoy_inputs[n].public_key()
+    }
+
+    pub(crate) fn decoy_commitment(&self, n: usize) -> G1Affine {
+        self.decoy_inputs[n].commitment()
+    }
+}
+
+/// A CLSAG ring signature over one input's ring of `(public_key, commitment)` pairs, proving
+/// knowledge of the secret key and commitment-to-zero blinding factor at the signer's hidden
+/// ring position without revealing which position that is.
+///
+/// Unlike the two-scalar MLSAG this replaces, a single response scalar `s` is carried per ring
+/// member (aggregated from the public key and commitment-to-zero responses via `mu_P`/`mu_C`),
+/// halving the signature's scalar count from `2 * ring_size` to `ring_size + 1`.
+#[derive(Debug)]
+pub struct MlsagSignature {
+    pub c0: Scalar,
+    pub s: Vec<Scalar>,
+    pub key_image: G1Affine,
+    /// `D = z * Hp(P)`: the key image of the commitment-to-zero secret, published as-is (no
+    /// cofactor-clearing dance — BLS12-381's G1 cofactor isn't 8, so dividing by 8 and checking
+    /// `8 * (D / 8)` is subgroup-free would be a no-op). Decoding via
+    /// [`from_bytes`][Self::from_bytes] already subgroup-checks every point through
+    /// `G1Affine::from_compressed`, but these fields are `pub`, so a signature built directly
+    /// (bypassing that path) is not covered by it; `clsag_verify`/`clsag_verify_batch` therefore
+    /// subgroup-check this field themselves rather than relying on the decode path alone.
+    pub commitment_key_image: G1Affine,
+    pub ring: Vec<(G1Affine, G1Affine)>,
+}
+
+impl MlsagSignature {
+    /// Encodes this signature as `c0 || s.len() || s || key_image || commitment_key_image ||
+    /// ring.len() || ring`, with every point compressed and every vector explicitly
+    /// length-prefixed so the ring's dimensions are self-describing in the wire format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.scalar(&self.c0);
+        w.len(self.s.len());
+        for s in &self.s {
+            w.scalar(s);
+        }
+        w.point(&self.key_image);
+        w.point(&self.commitment_key_image);
+        w.len(self.ring.len());
+        for (p, c) in &self.ring {
+            w.point(p);
+            w.point(c);
+        }
+        w.into_bytes()
+    }
+
+    /// Decodes a signature produced by [`to_bytes`][Self::to_bytes]. Every point is checked for
+    /// canonical, on-curve encoding and the `s`/`ring` dimensions are checked for consistency
+    /// before any group arithmetic is performed on the result.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let mut r = Reader::new(bytes);
+
+        let c0 = r.scalar()?;
+        let s_len = r.len_prefix(MAX_RING_SIZE)?;
+        let s = (0..s_len).map(|_| r.scalar()).collect::<crate::Result<Vec<_>>>()?;
+        let key_image = r.point()?;
+        let commitment_key_image = r.point()?;
+        let ring_len = r.len_prefix(MAX_RING_SIZE)?;
+        let ring = (0..ring_len)
+            .map(|_| Ok((r.point()?, r.point()?)))
+            .collect::<crate::Result<Vec<_>>>()?;
+        r.finish()?;
+
+        if ring.len() != s.len() {
+            return Err(Error::RingSizeMismatch {
+                expected: ring.len(),
+                got: s.len(),
+            });
+        }
+
+        Ok(Self {
+            c0,
+            s,
+            key_image,
+            commitment_key_image,
+            ring,
+        })
+    }
+}
+
+/// Derives the CLSAG aggregation coefficient for the given domain (`mu_P` or `mu_C`), binding it
+/// to every public key and commitment-to-zero point in the ring plus the key images and pseudo
+/// commitment, so a signer cannot swap in a different ring after the fact.
+pub(crate) fn clsag_mu(
+    domain: &'static [u8],
+    ring: &[(G1Affine, G1Affine)],
+    pseudo_commitment: G1Affine,
+    key_image: G1Affine,
+    commitment_key_image: G1Affine,
+) -> Scalar {
+    let mut transcript = Transcript::new(domain);
+    for (p, _) in ring {
+        transcript.append_point(b"ring_public_key", *p);
+    }
+    for (_, c) in ring {
+        let commitment_to_zero = G1Projective::from(*c) - G1Projective::from(pseudo_commitment);
+        transcript.append_point(b"ring_commitment_to_zero", commitment_to_zero.to_affine());
+    }
+    transcript.append_point(b"key_image", key_image);
+    transcript.append_point(b"commitment_key_image", commitment_key_image);
+    transcript.append_point(b"pseudo_commitment", pseudo_commitment);
+    transcript.challenge_scalar(b"mu")
+}
+
+/// Seeds a transcript once with the message and the full ring, so every step of the CLSAG
+/// challenge chain is guaranteed to absorb identical data in identical order; callers derive each
+/// step's challenge by cloning this base and appending that step's `(L, R)` pair.
+pub(crate) fn clsag_base_transcript(
+    ring: &[(G1Affine, G1Affine)],
+    pseudo_commitment: G1Affine,
+    msg: &[u8],
+) -> Transcript {
+    let mut transcript = Transcript::new(b"CLSAG_round");
+    for (p, c) in ring {
+        transcript.append_point(b"ring_public_key", *p);
+        transcript.append_point(b"ring_commitment", *c);
+    }
+    transcript.append_point(b"pseudo_commitment", pseudo_commitment);
+    transcript.append_message(b"msg", msg);
+    transcript
+}
+
+/// Derives the Fiat-Shamir challenge for one step of the CLSAG ring, from `base_transcript` (see
+/// [`clsag_base_transcript`]) and this step's `(L, R)` pair.
+pub(crate) fn clsag_round_challenge(
+    base_transcript: &Transcript,
+    l: G1Projective,
+    r: G1Projective,
+) -> Scalar {
+    let mut transcript = base_transcript.clone();
+    transcript.append_point(b"L", l.to_affine());
+    transcript.append_point(b"R", r.to_affine());
+    transcript.challenge_scalar(b"c")
+}
+
+/// Signs `msg` with a CLSAG ring signature over `ring`, proving the signer knows the secret key
+/// and commitment-to-zero blinding behind `ring[pi]` relative to `pseudo_commitment`.
+pub(crate) fn clsag_sign(
+    msg: &[u8],
+    input: &TrueInput,
+    revealed_pseudo_commitment: RevealedCommitment,
+    pi: usize,
+    ring: Vec<(G1Affine, G1Affine)>,
+    pseudo_commitment: G1Affine,
+    mut rng: impl RngCore,
+) -> MlsagSignature {
+    #[allow(non_snake_case)]
+    let G1 = G1Projective::generator();
+
+    // z is the secret behind the commitment-to-zero C_pi - pseudo_commitment
+    let z = input.revealed_commitment.blinding - revealed_pseudo_commitment.blinding;
+    assert_eq!(
+        G1Projective::from(ring[pi].1) - G1Projective::from(pseudo_commitment),
+        crate::pedersen_gens().commit(Scalar::zero(), z),
+    );
+
+    let key_image = input.key_image().to_affine();
+    let hp_pi = hash_to_curve(ring[pi].0.into());
+    let commitment_key_image = (hp_pi * z).to_affine();
+
+    let mu_p = clsag_mu(
+        b"CLSAG_agg_0",
+        &ring,
+        pseudo_commitment,
+        key_image,
+        commitment_key_image,
+    );
+    let mu_c = clsag_mu(
+        b"CLSAG_agg_1",
+        &ring,
+        pseudo_commitment,
+        key_image,
+        commitment_key_image,
+    );
+
+    let n = ring.len();
+    let base_transcript = clsag_base_transcript(&ring, pseudo_commitment, msg);
+    let alpha = Scalar::random(&mut rng);
+    let mut s: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let mut c: Vec<Scalar> = (0..n).map(|_| Scalar::zero()).collect();
+
+    let l_pi = G1 * alpha;
+    let r_pi = hp_pi * alpha;
+    c[(pi + 1) % n] = clsag_round_challenge(&base_transcript, l_pi, r_pi);
+
+    for offset in 1..n {
+        let i = (pi + offset) % n;
+        let (p_i, c_i) = ring[i];
+        let commitment_to_zero = G1Projective::from(c_i) - G1Projective::from(pseudo_commitment);
+        let l_i = G1 * s[i] + (G1Projective::from(p_i) * mu_p + commitment_to_zero * mu_c) * c[i];
+        let r_i = hash_to_curve(p_i.into()) * s[i]
+            + (G1Projective::from(key_image) * mu_p
+                + G1Projective::from(commitment_key_image) * mu_c)
+                * c[i];
+        c[(i + 1) % n] = clsag_round_challenge(&base_transcript, l_i, r_i);
+    }
+
+    s[pi] = alpha - c[pi] * (mu_p * input.secret_key + mu_c * z);
+
+    MlsagSignature {
+        c0: c[0],
+        s,
+        key_image,
+        commitment_key_image,
+        ring,
+    }
+}
+
+/// Verifies a CLSAG signature produced by [`clsag_sign`] against `pseudo_commitment`.
+pub(crate) fn clsag_verify(
+    msg: &[u8],
+    sig: &MlsagSignature,
+    pseudo_commitment: G1Affine,
+) -> bool {
+    #[allow(non_snake_case)]
+    let G1 = G1Projective::generator();
+    let n = sig.ring.len();
+
+    if n == 0 || sig.s.len() != n {
+        return false;
+    }
+    if !bool::from(sig.key_image.is_on_curve()) {
+        return false;
+    }
+    // `is_on_curve` alone doesn't check subgroup membership, and this field is `pub` so it isn't
+    // guaranteed to have passed through `from_bytes`'s subgroup-checked decode.
+    if !bool::from(sig.commitment_key_image.is_on_curve())
+        || !bool::from(sig.commitment_key_image.is_torsion_free())
+    {
+        return false;
+    }
+
+    let commitment_key_image = G1Projective::from(sig.commitment_key_image);
+
+    let mu_p = clsag_mu(
+        b"CLSAG_agg_0",
+        &sig.ring,
+        pseudo_commitment,
+        sig.key_image,
+        sig.commitment_key_image,
+    );
+    let mu_c = clsag_mu(
+        b"CLSAG_agg_1",
+        &sig.ring,
+        pseudo_commitment,
+        sig.key_image,
+        sig.commitment_key_image,
+    );
+
+    let base_transcript = clsag_base_transcript(&sig.ring, pseudo_commitment, msg);
+    let mut cprime = vec![Scalar::zero(); n];
+    cprime[0] = sig.c0;
+
+    for n_idx in 0..n {
+        let (p_n, c_n) = sig.ring[n_idx];
+        let commitment_to_zero = G1Projective::from(c_n) - G1Projective::from(pseudo_commitment);
+        let l_n = G1 * sig.s[n_idx]
+            + (G1Projective::from(p_n) * mu_p + commitment_to_zero * mu_c) * cprime[n_idx];
+        let r_n = hash_to_curve(p_n.into()) * sig.s[n_idx]
+            + (G1Projective::from(sig.key_image) * mu_p + commitment_key_image * mu_c)
+                * cprime[n_idx];
+        cprime[(n_idx + 1) % n] = clsag_round_challenge(&base_transcript, l_n, r_n);
+    }
+
+    sig.c0 == cprime[0]
+}
+
+/// Caches `Hp(P)` per distinct public key, so batch verification never recomputes the same
+/// hash-to-curve map twice no matter how many signatures or ring slots reference that key.
+struct HpCache(HashMap<[u8; 48], G1Projective>);
+
+impl HpCache {
+    fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    fn get(&mut self, p: G1Affine) -> G1Projective {
+        *self
+            .0
+            .entry(p.to_compressed())
+            .or_insert_with(|| hash_to_curve(p.into()))
+    }
+}
+
+/// Verifies many CLSAG signatures at once. Each ring step's `L` and `R` point equations are each
+/// folded into one [`G1Projective::multi_exp`] call instead of several independent scalar
+/// multiplications followed by additions, and `Hp(P)` is computed once per distinct public key
+/// via [`HpCache`] and reused across every signature and ring slot that references it.
+///
+/// Returns the indices (into `entries`) of the signatures that failed to verify.
+pub(crate) fn clsag_verify_batch(entries: &[(&[u8], &MlsagSignature, G1Affine)]) -> Vec<usize> {
+    #[allow(non_snake_case)]
+    let g1_affine = G1Affine::from(G1Projective::generator());
+    let mut hp_cache = HpCache::new();
+    let mut failed = Vec::new();
+
+    for (idx, (msg, sig, pseudo_commitment)) in entries.iter().enumerate() {
+        let n = sig.ring.len();
+        if n == 0 || sig.s.len() != n {
+            failed.push(idx);
+            continue;
+        }
+        // `is_on_curve` alone doesn't check subgroup membership, and this field is `pub` so it
+        // isn't guaranteed to have passed through `from_bytes`'s subgroup-checked decode.
+        if !bool::from(sig.key_image.is_on_curve())
+            || !bool::from(sig.commitment_key_image.is_on_curve())
+            || !bool::from(sig.commitment_key_image.is_torsion_free())
+        {
+            failed.push(idx);
+            continue;
+        }
+
+        let commitment_key_image = G1Projective::from(sig.commitment_key_image);
+
+        let mu_p = clsag_mu(
+            b"CLSAG_agg_0",
+            &sig.ring,
+            *pseudo_commitment,
+            sig.key_image,
+            sig.commitment_key_image,
+        );
+        let mu_c = clsag_mu(
+            b"CLSAG_agg_1",
+            &sig.ring,
+            *pseudo_commitment,
+            sig.key_image,
+            sig.commitment_key_image,
+        );
+
+        let base_transcript = clsag_base_transcript(&sig.ring, *pseudo_commitment, msg);
+        let mut cprime = vec![Scalar::zero(); n];
+        cprime[0] = sig.c0;
+
+        for n_idx in 0..n {
+            let (p_n, c_n) = sig.ring[n_idx];
+            let commitment_to_zero =
+                (G1Projective::from(c_n) - G1Projective::from(*pseudo_commitment)).to_affine();
+            let hp_n = hp_cache.get(p_n).to_affine();
+
+            let scaled_mu_p = mu_p * cprime[n_idx];
+            let scaled_mu_c = mu_c * cprime[n_idx];
+
+            let l_n = G1Projective::multi_exp(
+                &[g1_affine, p_n, commitment_to_zero],
+                &[sig.s[n_idx], scaled_mu_p, scaled_mu_c],
+            );
+            let r_n = G1Projective::multi_exp(
+                &[hp_n, sig.key_image, commitment_key_image.to_affine()],
+                &[sig.s[n_idx], scaled_mu_p, scaled_mu_c],
+            );
+
+            cprime[(n_idx + 1) % n] = clsag_round_challenge(&base_transcript, l_n, r_n);
+        }
+
+        if sig.c0 != cprime[0] {
+            failed.push(idx);
+        }
+    }
+
+    failed
+}
+
+/// Hashes given material to a Scalar, repeated hashing is used if a hash can not be interpreted as a Scalar
+pub(crate) fn hash_to_scalar(material: &[&[u8]]) -> Scalar {
+    let mut sha3 = Sha3::v256();
+    for chunk in material {
+        sha3.update(chunk);
+    }
+    let mut hash = [0u8; 32];
+    sha3.finalize(&mut hash);
+    loop {
+        let s_opt = Scalar::from_bytes_le(&hash);
+        if bool::from(s_opt.is_some()) {
+            return s_opt.unwrap();
+        }
+
+        let mut sha3 = Sha3::v256();
+        sha3.update(&hash);
+        sha3.finalize(&mut hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::RevealedCommitment;
+
+    fn sign_example(mut rng: impl RngCore) -> MlsagSignature {
+        let input = TrueInput {
+            secret_key: Scalar::random(&mut rng),
+            revealed_commitment: RevealedCommitment::from_value(3, &mut rng),
+        };
+        let pseudo_commitment = RevealedCommitment::from_value(3, &mut rng);
+        let pi = 1;
+        let ring = vec![
+            (
+                G1Projective::generator().to_affine(),
+                G1Projective::generator().to_affine(),
+            ),
+            (
+                input.public_key().to_affine(),
+                input.revealed_commitment.commit(&crate::pedersen_gens()).to_affine(),
+            ),
+        ];
+        let pseudo_commitment_point = pseudo_commitment.commit(&crate::pedersen_gens()).to_affine();
+        clsag_sign(
+            b"msg",
+            &input,
+            pseudo_commitment,
+            pi,
+            ring,
+            pseudo_commitment_point,
+            &mut rng,
+        )
+    }
+
+    #[test]
+    fn to_bytes_from_bytes_roundtrip() {
+        let sig = sign_example(OsRng);
+        let decoded = MlsagSignature::from_bytes(&sig.to_bytes()).unwrap();
+        assert_eq!(sig.to_bytes(), decoded.to_bytes());
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_input() {
+        let sig = sign_example(OsRng);
+        let mut bytes = sig.to_bytes();
+        bytes.truncate(bytes.len() - 1);
+        assert!(MlsagSignature::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn from_bytes_rejects_oversized_ring_length() {
+        let sig = sign_example(OsRng);
+        let mut bytes = sig.to_bytes();
+        // Overwrite the `s.len()` prefix (right after the 32-byte c0 scalar) with a value beyond
+        // MAX_RING_SIZE.
+        bytes[32..40].copy_from_slice(&((MAX_RING_SIZE as u64) + 1).to_le_bytes());
+        assert!(MlsagSignature::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+//! Threshold multisig signing: N parties jointly hold a spend key behind a Pedersen-VSS
+//! distributed key generation and collaboratively produce a single [`MlsagSignature`][crate::mlsag::MlsagSignature]
+//! via a two-round distributed CLSAG, without any one party ever learning the group secret.
+//!
+//! The output of the signing protocol has the same form and the same key images as a signature
+//! from a single party holding the full spend key, so
+//! [`clsag_verify`][crate::mlsag::clsag_verify] accepts it unchanged.
+
+use std::collections::BTreeMap;
+
+use blstrs::{
+    group::{ff::Field, Curve, Group},
+    G1Affine, G1Projective, Scalar,
+};
+use rand_core::RngCore;
+
+use crate::mlsag::{
+    clsag_base_transcript, clsag_mu, clsag_round_challenge, hash_to_curve, hash_to_scalar,
+};
+
+/// A participant's 1-based index within a DKG/signing session. Index `0` is reserved for the
+/// implicit secret at the constant term of every polynomial.
+pub type PartyId = u32;
+
+fn party_scalar(id: PartyId) -> Scalar {
+    Scalar::from(u64::from(id))
+}
+
+/// The Lagrange coefficient `lambda_id` such that `sum_{id in signing_set} lambda_id * F(id) ==
+/// F(0)` for any degree-`|signing_set| - 1` polynomial `F`. Every party's Pedersen-VSS share is a
+/// point `F(id)` on the dealer's polynomial, so shares must be weighted by this coefficient before
+/// being combined — summing raw shares only reconstructs `F(0)` by coincidence.
+fn lagrange_coefficient(signing_set: &[PartyId], id: PartyId) -> Scalar {
+    let x_i = party_scalar(id);
+    signing_set
+        .iter()
+        .filter(|&&other| other != id)
+        .map(|&other| {
+            let x_j = party_scalar(other);
+            x_j * (x_j - x_i).invert().unwrap()
+        })
+        .product()
+}
+
+/// A party's private degree-`t` polynomial, used once to deal Pedersen-VSS shares.
+pub struct Polynomial(Vec<Scalar>);
+
+impl Polynomial {
+    /// Samples a fresh random polynomial of degree `threshold` (so `threshold + 1` parties are
+    /// required to reconstruct the constant term).
+    pub fn random(threshold: usize, mut rng: impl RngCore) -> Self {
+        Self((0..=threshold).map(|_| Scalar::random(&mut rng)).collect())
+    }
+
+    /// This party's contribution to the group public key, `g * a_0`.
+    pub fn public_term(&self) -> G1Affine {
+        (G1Projective::generator() * self.0[0]).to_affine()
+    }
+
+    /// Evaluates the polynomial at `id`, producing the raw (unencrypted) share owed to that
+    /// party.
+    pub fn share_for(&self, id: PartyId) -> Scalar {
+        let x = party_scalar(id);
+        let mut acc = Scalar::zero();
+        for coeff in self.0.iter().rev() {
+            acc = acc * x + coeff;
+        }
+        acc
+    }
+
+    /// The `g * a_j` commitments to every coefficient, broadcast so recipients can verify their
+    /// share without trusting the dealer.
+    pub fn commitments(&self) -> VssCommitments {
+        VssCommitments(
+            self.0
+                .iter()
+                .map(|a| (G1Projective::generator() * a).to_affine())
+                .collect(),
+        )
+    }
+}
+
+/// The `{g * a_j}` commitments a dealer broadcasts alongside its encrypted shares.
+#[derive(Debug, Clone)]
+pub struct VssCommitments(Vec<G1Affine>);
+
+impl VssCommitments {
+    /// Checks `share` is consistent with these commitments for party `id`, i.e. that
+    /// `g * share == sum_j commitments[j] * id^j`.
+    pub fn verify_share(&self, id: PartyId, share: Scalar) -> bool {
+        let x = party_scalar(id);
+        let mut expected = G1Projective::identity();
+        let mut x_pow = Scalar::one();
+        for commitment in &self.0 {
+            expected += G1Projective::from(*commitment) * x_pow;
+            x_pow *= x;
+        }
+        G1Projective::generator() * share == expected
+    }
+
+    fn public_term(&self) -> G1Affine {
+        self.0[0]
+    }
+}
+
+/// A share encrypted for a single recipient via a Diffie-Hellman one-time pad: the dealer picks
+/// an ephemeral secret `e`, publishes `e * G`, and masks the share with
+/// `hash_to_scalar(e * recipient_pk)`.
+#[derive(Debug, Clone)]
+pub struct EncryptedShare {
+    ephemeral_public_key: G1Affine,
+    masked_share: Scalar,
+}
+
+fn shared_secret(shared_point: G1Projective) -> Scalar {
+    hash_to_scalar(&[b"multisig-share-encryption", &shared_point.to_compressed()])
+}
+
+/// Encrypts `share` (owed to `recipient_public_key`) so only that recipient's secret key can
+/// recover it.
+pub fn encrypt_share(
+    share: Scalar,
+    recipient_public_key: G1Affine,
+    mut rng: impl RngCore,
+) -> EncryptedShare {
+    let ephemeral_secret = Scalar::random(&mut rng);
+    let shared_point = G1Projective::from(recipient_public_key) * ephemeral_secret;
+    EncryptedShare {
+        ephemeral_public_key: (G1Projective::generator() * ephemeral_secret).to_affine(),
+        masked_share: share + shared_secret(shared_point),
+    }
+}
+
+/// Recovers the share a dealer encrypted for us with [`encrypt_share`].
+pub fn decrypt_share(encrypted: &EncryptedShare, recipient_secret_key: Scalar) -> Scalar {
+    let shared_point = G1Projective::from(encrypted.ephemeral_public_key) * recipient_secret_key;
+    encrypted.masked_share - shared_secret(shared_point)
+}
+
+/// This party's Pedersen-VSS share of a DKG'd secret (the sum of every dealer's polynomial at this
+/// party's id), plus the group's aggregated public key.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyShare {
+    pub party_id: PartyId,
+    pub secret_share: Scalar,
+    pub group_public_key: G1Affine,
+}
+
+/// Combines the decrypted shares received from every dealer (including our own) into our share
+/// `x_i` of the group secret (weighted by a Lagrange coefficient at signing time, see
+/// [`Signer::round_one`]), and sums every dealer's `commitments` to recover the
+/// group public key `X = sum_i g * a_{i,0}`.
+pub fn finalize_key_share(
+    party_id: PartyId,
+    received_shares: &[Scalar],
+    dealer_commitments: &[VssCommitments],
+) -> KeyShare {
+    let secret_share = received_shares.iter().sum();
+    let group_public_key = dealer_commitments
+        .iter()
+        .map(|c| G1Projective::from(c.public_term()))
+        .sum::<G1Projective>()
+        .to_affine();
+
+    KeyShare {
+        party_id,
+        secret_share,
+        group_public_key,
+    }
+}
+
+/// This signer's round-one contribution: nonce commitments and a share of the key image(s),
+/// broadcast to the coordinator.
+#[derive(Debug, Clone, Copy)]
+pub struct SignerRoundOne {
+    party_id: PartyId,
+    l_share: G1Projective,
+    r_share: G1Projective,
+    key_image_share: G1Projective,
+    commitment_key_image_share: G1Projective,
+}
+
+impl SignerRoundOne {
+    pub fn l_share(&self) -> G1Affine {
+        self.l_share.to_affine()
+    }
+
+    pub fn r_share(&self) -> G1Affine {
+        self.r_share.to_affine()
+    }
+
+    pub fn key_image_share(&self) -> G1Affine {
+        self.key_image_share.to_affine()
+    }
+
+    pub fn commitment_key_image_share(&self) -> G1Affine {
+        self.commitment_key_image_share.to_affine()
+    }
+}
+
+/// Holds the per-signer secret state between round one and round two.
+pub struct Signer {
+    key_share: KeyShare,
+    /// This signer's Lagrange-weighted share of the group secret, `lambda_id * x_id`.
+    effective_secret_share: Scalar,
+    /// This signer's Lagrange-weighted share of `z = input.blinding - pseudo.blinding`.
+    effective_commitment_share: Scalar,
+    alpha: Scalar,
+    hp_signer_key: G1Projective,
+}
+
+impl Signer {
+    /// Starts round one: samples this signer's nonce `alpha_i` and publishes
+    /// `alpha_i * G`, `alpha_i * Hp(P_pi)` and this signer's shares of the key images.
+    ///
+    /// `key_share.secret_share` and `commitment_share` are Pedersen-VSS shares — points on the
+    /// dealers' polynomials at this party's id — so they're weighted by this party's Lagrange
+    /// coefficient for `signing_set` before use, the same way Shamir reconstruction always
+    /// requires combining shares with their interpolation coefficients rather than summing them
+    /// raw.
+    pub fn round_one(
+        key_share: KeyShare,
+        commitment_share: Scalar,
+        signing_set: &[PartyId],
+        mut rng: impl RngCore,
+    ) -> (Self, SignerRoundOne) {
+        let hp_signer_key = hash_to_curve(key_share.group_public_key.into());
+        let alpha = Scalar::random(&mut rng);
+
+        let lambda = lagrange_coefficient(signing_set, key_share.party_id);
+        let effective_secret_share = lambda * key_share.secret_share;
+        let effective_commitment_share = lambda * commitment_share;
+
+        let round_one = SignerRoundOne {
+            party_id: key_share.party_id,
+            l_share: G1Projective::generator() * alpha,
+            r_share: hp_signer_key * alpha,
+            key_image_share: hp_signer_key * effective_secret_share,
+            commitment_key_image_share: hp_signer_key * effective_commitment_share,
+        };
+
+        (
+            Self {
+                key_share,
+                effective_secret_share,
+                effective_commitment_share,
+                alpha,
+                hp_signer_key,
+            },
+            round_one,
+        )
+    }
+
+    /// Finishes round two once the coordinator has fixed the Fiat-Shamir challenge `c_pi`,
+    /// returning this signer's partial response `s_i`.
+    pub fn round_two(&self, mu_p: Scalar, mu_c: Scalar, c_pi: Scalar) -> Scalar {
+        self.alpha
+            - c_pi * (mu_p * self.effective_secret_share + mu_c * self.effective_commitment_share)
+    }
+}
+
+/// Coordinates a distributed CLSAG signature: aggregates every signer's round-one contribution
+/// into the ring's signer-slot nonces and key images, runs the usual CLSAG challenge chain around
+/// the non-signer ring positions to fix `c_pi`, then sums every signer's round-two response into
+/// the final response scalar at `pi`.
+pub struct Coordinator {
+    ring: Vec<(G1Affine, G1Affine)>,
+    pi: usize,
+    key_image: G1Affine,
+    commitment_key_image: G1Affine,
+    mu_p: Scalar,
+    mu_c: Scalar,
+    c: Vec<Scalar>,
+    s: Vec<Scalar>,
+}
+
+impl Coordinator {
+    /// Aggregates every signer's [`SignerRoundOne`] contribution and runs the CLSAG challenge
+    /// chain around the ring to fix the challenge `c_pi` the signers must respond to.
+    pub fn new(
+        msg: &[u8],
+        ring: Vec<(G1Affine, G1Affine)>,
+        pseudo_commitment: G1Affine,
+        pi: usize,
+        contributions: &BTreeMap<PartyId, SignerRoundOne>,
+        mut rng: impl RngCore,
+    ) -> Self {
+        #[allow(non_snake_case)]
+        let G1 = G1Projective::generator();
+        let n = ring.len();
+
+        let l_pi: G1Projective = contributions.values().map(|c| c.l_share).sum();
+        let r_pi: G1Projective = contributions.values().map(|c| c.r_share).sum();
+        let key_image = contributions
+            .values()
+            .map(|c| c.key_image_share)
+            .sum::<G1Projective>()
+            .to_affine();
+        let commitment_key_image = contributions
+            .values()
+            .map(|c| c.commitment_key_image_share)
+            .sum::<G1Projective>();
+        let commitment_key_image_affine = commitment_key_image.to_affine();
+
+        let mu_p = clsag_mu(
+            b"CLSAG_agg_0",
+            &ring,
+            pseudo_commitment,
+            key_image,
+            commitment_key_image_affine,
+        );
+        let mu_c = clsag_mu(
+            b"CLSAG_agg_1",
+            &ring,
+            pseudo_commitment,
+            key_image,
+            commitment_key_image_affine,
+        );
+
+        let base_transcript = clsag_base_transcript(&ring, pseudo_commitment, msg);
+
+        let mut s: Vec<Scalar> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let mut c: Vec<Scalar> = (0..n).map(|_| Scalar::zero()).collect();
+
+        c[(pi + 1) % n] = clsag_round_challenge(&base_transcript, l_pi, r_pi);
+
+        for offset in 1..n {
+            let i = (pi + offset) % n;
+            let (p_i, c_i) = ring[i];
+            let commitment_to_zero =
+                G1Projective::from(c_i) - G1Projective::from(pseudo_commitment);
+            let l_i =
+                G1 * s[i] + (G1Projective::from(p_i) * mu_p + commitment_to_zero * mu_c) * c[i];
+            let r_i = hash_to_curve(p_i.into()) * s[i]
+                + (G1Projective::from(key_image) * mu_p + commitment_key_image * mu_c) * c[i];
+            c[(i + 1) % n] = clsag_round_challenge(&base_transcript, l_i, r_i);
+        }
+
+        Self {
+            ring,
+            pi,
+            key_image,
+            commitment_key_image: commitment_key_image_affine,
+            mu_p,
+            mu_c,
+            c,
+            s,
+        }
+    }
+
+    /// The aggregation coefficients and challenge `(mu_p, mu_c, c_pi)` every signer needs to
+    /// compute its round-two response.
+    pub fn challenge(&self) -> (Scalar, Scalar, Scalar) {
+        (self.mu_p, self.mu_c, self.c[self.pi])
+    }
+
+    /// Sums every signer's round-two response into the final response scalar at `pi` and
+    /// assembles the completed [`MlsagSignature`][crate::mlsag::MlsagSignature].
+    pub fn finalize(mut self, partial_responses: &[Scalar]) -> crate::mlsag::MlsagSignature {
+        self.s[self.pi] = partial_responses.iter().sum();
+
+        crate::mlsag::MlsagSignature {
+            c0: self.c[0],
+            s: self.s,
+            key_image: self.key_image,
+            commitment_key_image: self.commitment_key_image,
+            ring: self.ring,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::mlsag::clsag_verify;
+    use crate::RevealedCommitment;
+
+    #[test]
+    fn distributed_sign_matches_single_key_verification() {
+        let mut rng = OsRng;
+
+        // Two parties, both required to reconstruct the group secret (threshold degree 1).
+        let party_ids: Vec<PartyId> = vec![1, 2];
+
+        let secret_poly_a = Polynomial::random(1, &mut rng);
+        let secret_poly_b = Polynomial::random(1, &mut rng);
+        let commitment_poly_a = Polynomial::random(1, &mut rng);
+        let commitment_poly_b = Polynomial::random(1, &mut rng);
+
+        let dealer_commitments = [secret_poly_a.commitments(), secret_poly_b.commitments()];
+
+        let key_share_1 = finalize_key_share(
+            1,
+            &[secret_poly_a.share_for(1), secret_poly_b.share_for(1)],
+            &dealer_commitments,
+        );
+        let key_share_2 = finalize_key_share(
+            2,
+            &[secret_poly_a.share_for(2), secret_poly_b.share_for(2)],
+            &dealer_commitments,
+        );
+
+        let commitment_share_1 = commitment_poly_a.share_for(1) + commitment_poly_b.share_for(1);
+        let commitment_share_2 = commitment_poly_a.share_for(2) + commitment_poly_b.share_for(2);
+
+        // Known here only so the test can check the distributed signature against what a single
+        // key holder would have produced; no party ever learns these values.
+        let group_secret = secret_poly_a.share_for(0) + secret_poly_b.share_for(0);
+        let group_z = commitment_poly_a.share_for(0) + commitment_poly_b.share_for(0);
+        assert_eq!(
+            key_share_1.group_public_key,
+            (G1Projective::generator() * group_secret).to_affine()
+        );
+
+        let pseudo_commitment = RevealedCommitment::from_value(3, &mut rng);
+        let commitment = RevealedCommitment {
+            value: 3,
+            blinding: pseudo_commitment.blinding + group_z,
+        };
+        let pc_gens = crate::pedersen_gens();
+        let pseudo_commitment_point = pseudo_commitment.commit(&pc_gens).to_affine();
+
+        let pi = 1;
+        let ring = vec![
+            (
+                G1Projective::generator().to_affine(),
+                G1Projective::generator().to_affine(),
+            ),
+            (
+                key_share_1.group_public_key,
+                commitment.commit(&pc_gens).to_affine(),
+            ),
+        ];
+
+        let (signer_1, round_one_1) =
+            Signer::round_one(key_share_1, commitment_share_1, &party_ids, &mut rng);
+        let (signer_2, round_one_2) =
+            Signer::round_one(key_share_2, commitment_share_2, &party_ids, &mut rng);
+
+        let mut contributions = BTreeMap::new();
+        contributions.insert(1, round_one_1);
+        contributions.insert(2, round_one_2);
+
+        let coordinator = Coordinator::new(
+            b"msg",
+            ring,
+            pseudo_commitment_point,
+            pi,
+            &contributions,
+            &mut rng,
+        );
+        let (mu_p, mu_c, c_pi) = coordinator.challenge();
+
+        let response_1 = signer_1.round_two(mu_p, mu_c, c_pi);
+        let response_2 = signer_2.round_two(mu_p, mu_c, c_pi);
+
+        let sig = coordinator.finalize(&[response_1, response_2]);
+
+        assert!(clsag_verify(b"msg", &sig, pseudo_commitment_point));
+
+        // The distributed signature's key image matches what a single party holding
+        // `group_secret` would have produced.
+        let single_key_image = hash_to_curve(key_share_1.group_public_key.into()) * group_secret;
+        assert_eq!(sig.key_image, single_key_image.to_affine());
+    }
+}
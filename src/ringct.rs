@@ -0,0 +1,446 @@
+use std::collections::BTreeSet;
+
+use blstrs::{
+    group::{ff::Field, Curve},
+    G1Affine, G1Projective, Scalar,
+};
+use bulletproofs::{BulletproofGens, RangeProof};
+use merlin::Transcript;
+use rand_core::RngCore;
+
+use crate::codec::{Reader, Writer};
+use crate::mlsag::{self, MlsagMaterial, MlsagSignature};
+use crate::{Error, RevealedCommitment};
+
+/// Number of bits each output amount is range-proved over; amounts are `u64`s so `[0, 2^64)`.
+const RANGE_PROOF_BITS: usize = 64;
+
+/// Upper bounds on the number of inputs/outputs a decoded [`RingCtSignature`] is allowed to
+/// claim, so a malicious length prefix can't drive an oversized allocation before its contents
+/// are even read.
+const MAX_INPUTS: usize = 4096;
+const MAX_OUTPUTS: usize = 4096;
+const MAX_MLSAG_BYTES: usize = 1 << 20;
+const MAX_RANGE_PROOF_BYTES: usize = 1 << 20;
+
+/// Bulletproofs aggregation requires a power-of-two number of parties, so proofs over a
+/// non-power-of-two output count are padded with zero-value, zero-blinding commitments that both
+/// prover and verifier can reconstruct deterministically.
+fn padded_range_proof_count(n_outputs: usize) -> usize {
+    n_outputs.next_power_of_two().max(1)
+}
+
+pub struct Output {
+    pub public_key: G1Affine,
+    pub amount: u64,
+}
+
+impl Output {
+    fn public_key(&self) -> G1Affine {
+        self.public_key
+    }
+
+    fn amount(&self) -> u64 {
+        self.amount
+    }
+}
+
+pub struct RingCtMaterial {
+    pub inputs: Vec<MlsagMaterial>,
+    pub outputs: Vec<Output>,
+}
+
+#[derive(Debug)]
+pub struct RingCtSignature {
+    mlsags: Vec<MlsagSignature>,
+    pseudo_commitments: Vec<G1Affine>,
+    output_commitments: Vec<G1Affine>,
+    range_proof: RangeProof,
+}
+
+impl RingCtSignature {
+    /// Encodes this signature as a single self-describing blob: every input's CLSAG (each
+    /// already self-describing via [`MlsagSignature::to_bytes`]) followed by the pseudo and
+    /// output commitments and the aggregated range proof, with every vector explicitly
+    /// length-prefixed.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+
+        w.len(self.mlsags.len());
+        for mlsag in &self.mlsags {
+            w.blob(&mlsag.to_bytes());
+        }
+
+        w.len(self.pseudo_commitments.len());
+        for c in &self.pseudo_commitments {
+            w.point(c);
+        }
+
+        w.len(self.output_commitments.len());
+        for c in &self.output_commitments {
+            w.point(c);
+        }
+
+        w.blob(&self.range_proof.to_bytes());
+
+        w.into_bytes()
+    }
+
+    /// Decodes a signature produced by [`to_bytes`][Self::to_bytes]. Every nested CLSAG and every
+    /// point is validated on decode, and the number of CLSAGs is checked against the number of
+    /// pseudo commitments, before any group arithmetic is performed on the result.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let mut r = Reader::new(bytes);
+
+        let mlsags_len = r.len_prefix(MAX_INPUTS)?;
+        let mlsags = (0..mlsags_len)
+            .map(|_| MlsagSignature::from_bytes(r.blob(MAX_MLSAG_BYTES)?))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let pseudo_len = r.len_prefix(MAX_INPUTS)?;
+        let pseudo_commitments = (0..pseudo_len)
+            .map(|_| r.point())
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let output_len = r.len_prefix(MAX_OUTPUTS)?;
+        let output_commitments = (0..output_len)
+            .map(|_| r.point())
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let range_proof = RangeProof::from_bytes(r.blob(MAX_RANGE_PROOF_BYTES)?)
+            .map_err(|_| Error::MalformedEncoding)?;
+
+        r.finish()?;
+
+        if mlsags.len() != pseudo_commitments.len() {
+            return Err(Error::RingSizeMismatch {
+                expected: mlsags.len(),
+                got: pseudo_commitments.len(),
+            });
+        }
+
+        Ok(Self {
+            mlsags,
+            pseudo_commitments,
+            output_commitments,
+            range_proof,
+        })
+    }
+}
+
+impl RingCtMaterial {
+    pub fn sign(&self, msg: &[u8], mut rng: impl RngCore) -> RingCtSignature {
+        let ring_size = self.inputs[0].ring_size();
+        for input in self.inputs.iter() {
+            assert_eq!(input.ring_size(), ring_size);
+        }
+
+        let pi = rng.next_u32() as usize % ring_size;
+        let pc_gens = crate::pedersen_gens();
+
+        let revealed_pseudo_commitments = Vec::from_iter(self.inputs.iter().map(|input| {
+            RevealedCommitment {
+                value: input.true_input.revealed_commitment.value,
+                blinding: Scalar::random(&mut rng),
+            }
+        }));
+
+        let revealed_output_commitments = {
+            let mut commitments = Vec::from_iter(
+                self.outputs
+                    .iter()
+                    .take(self.outputs.len() - 1)
+                    .map(Output::amount)
+                    .map(|value| RevealedCommitment {
+                        value,
+                        blinding: Scalar::random(&mut rng),
+                    }),
+            );
+
+            let output_blinding_correction = revealed_pseudo_commitments
+                .iter()
+                .map(RevealedCommitment::blinding)
+                .sum::<Scalar>()
+                - commitments
+                    .iter()
+                    .map(RevealedCommitment::blinding)
+                    .sum::<Scalar>();
+
+            if let Some(last_output) = self.outputs.last() {
+                commitments.push(RevealedCommitment {
+                    value: last_output.amount,
+                    blinding: output_blinding_correction,
+                });
+            } else {
+                panic!("Expected at least one output")
+            }
+
+            commitments
+        };
+
+        let pseudo_commitments = Vec::from_iter(
+            revealed_pseudo_commitments
+                .iter()
+                .map(|c| c.commit(&pc_gens)),
+        );
+        assert_eq!(
+            pseudo_commitments.iter().sum::<G1Projective>(),
+            revealed_output_commitments
+                .iter()
+                .map(|c| c.commit(&pc_gens))
+                .sum()
+        );
+
+        // We create a CLSAG ring signature for each input
+        let mut mlsags = Vec::new();
+        for (input, revealed_pseudo_commitment) in
+            self.inputs.iter().zip(revealed_pseudo_commitments.iter())
+        {
+            let mut ring = Vec::from_iter(
+                (0..input.decoys_len())
+                    .map(|n| (input.decoy_public_key(n), input.decoy_commitment(n))),
+            );
+            ring.insert(
+                pi,
+                (
+                    input.public_key(),
+                    input.true_input.revealed_commitment.commit(&pc_gens).to_affine(),
+                ),
+            );
+
+            let pseudo_commitment = revealed_pseudo_commitment.commit(&pc_gens).to_affine();
+            let mlsag_sig = mlsag::clsag_sign(
+                msg,
+                &input.true_input,
+                *revealed_pseudo_commitment,
+                pi,
+                ring,
+                pseudo_commitment,
+                &mut rng,
+            );
+            mlsags.push(mlsag_sig);
+        }
+
+        // Prove every output amount lies in [0, 2^64) with a single aggregated (standard)
+        // Bulletproofs range proof, padding with zero-value/zero-blinding commitments up to the
+        // next power of two (required by the aggregation protocol). Only the real output
+        // commitments are kept.
+        let n_outputs = revealed_output_commitments.len();
+        let padded_len = padded_range_proof_count(n_outputs);
+        let mut values: Vec<u64> = revealed_output_commitments
+            .iter()
+            .map(|c| c.value)
+            .collect();
+        let mut blindings: Vec<Scalar> = revealed_output_commitments
+            .iter()
+            .map(|c| c.blinding)
+            .collect();
+        values.resize(padded_len, 0);
+        blindings.resize(padded_len, Scalar::zero());
+
+        let bp_gens = BulletproofGens::new(RANGE_PROOF_BITS, padded_len);
+        let mut prover_transcript = Transcript::new(b"sn_ringct output range proof");
+        let (range_proof, padded_commitments) = RangeProof::prove_multiple(
+            &bp_gens,
+            &pc_gens,
+            &mut prover_transcript,
+            &values,
+            &blindings,
+            RANGE_PROOF_BITS,
+        )
+        .expect("failed to build output range proof");
+
+        RingCtSignature {
+            mlsags,
+            pseudo_commitments: Vec::from_iter(pseudo_commitments.iter().map(|c| c.to_affine())),
+            output_commitments: Vec::from_iter(padded_commitments.into_iter().take(n_outputs)),
+            range_proof,
+        }
+    }
+}
+
+pub fn verify(msg: &[u8], sig: &RingCtSignature) -> bool {
+    if sig.mlsags.len() != sig.pseudo_commitments.len() {
+        return false;
+    }
+
+    for (mlsag_sig, pseudo_commitment) in sig.mlsags.iter().zip(sig.pseudo_commitments.iter()) {
+        if !mlsag::clsag_verify(msg, mlsag_sig, *pseudo_commitment) {
+            return false;
+        }
+    }
+
+    balances_and_range_proof_are_valid(sig)
+}
+
+/// Verifies many [`RingCtSignature`]s at once. Every input's CLSAG ring-equation check, across
+/// every signature in `entries`, is folded into a single call to
+/// [`mlsag::clsag_verify_batch`][crate::mlsag::clsag_verify_batch] so a repeated ring member's
+/// `Hp(P)` is only computed once no matter how many signatures reference it.
+///
+/// Returns the indices (into `entries`) of the signatures that failed to verify.
+pub fn verify_batch(entries: &[(&[u8], &RingCtSignature)]) -> Vec<usize> {
+    let mut failed = BTreeSet::new();
+
+    // Flatten every input's CLSAG across every signature into one batched call, remembering which
+    // top-level entry each flattened input came from.
+    let mut clsag_entries = Vec::new();
+    let mut owning_entry = Vec::new();
+    for (entry_idx, (msg, sig)) in entries.iter().enumerate() {
+        if sig.mlsags.len() != sig.pseudo_commitments.len() {
+            failed.insert(entry_idx);
+            continue;
+        }
+        for (mlsag_sig, pseudo_commitment) in sig.mlsags.iter().zip(sig.pseudo_commitments.iter())
+        {
+            clsag_entries.push((*msg, mlsag_sig, *pseudo_commitment));
+            owning_entry.push(entry_idx);
+        }
+    }
+
+    for flattened_idx in mlsag::clsag_verify_batch(&clsag_entries) {
+        failed.insert(owning_entry[flattened_idx]);
+    }
+
+    for (entry_idx, (_msg, sig)) in entries.iter().enumerate() {
+        if !failed.contains(&entry_idx) && !balances_and_range_proof_are_valid(sig) {
+            failed.insert(entry_idx);
+        }
+    }
+
+    failed.into_iter().collect()
+}
+
+/// Checks that the pseudo commitments sum to the output commitments (so blinding factors cancel
+/// and amounts balance) and that the aggregated output range proof verifies.
+fn balances_and_range_proof_are_valid(sig: &RingCtSignature) -> bool {
+    let pseudo_sum: G1Projective = sig.pseudo_commitments.iter().map(G1Projective::from).sum();
+    let output_sum: G1Projective = sig.output_commitments.iter().map(G1Projective::from).sum();
+    if pseudo_sum != output_sum {
+        return false;
+    }
+
+    let pc_gens = crate::pedersen_gens();
+    let padded_len = padded_range_proof_count(sig.output_commitments.len());
+    let mut commitments = sig.output_commitments.clone();
+    commitments.resize(
+        padded_len,
+        pc_gens.commit(Scalar::zero(), Scalar::zero()).to_affine(),
+    );
+
+    let bp_gens = BulletproofGens::new(RANGE_PROOF_BITS, padded_len);
+    let mut verifier_transcript = Transcript::new(b"sn_ringct output range proof");
+    if sig
+        .range_proof
+        .verify_multiple(
+            &bp_gens,
+            &pc_gens,
+            &mut verifier_transcript,
+            &commitments,
+            RANGE_PROOF_BITS,
+        )
+        .is_err()
+    {
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use blstrs::group::Group;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::mlsag::{DecoyInput, TrueInput};
+
+    fn test_material(amount: u64, mut rng: impl RngCore) -> RingCtMaterial {
+        RingCtMaterial {
+            inputs: vec![MlsagMaterial {
+                true_input: TrueInput {
+                    secret_key: Scalar::random(&mut rng),
+                    revealed_commitment: RevealedCommitment::from_value(amount, &mut rng),
+                },
+                decoy_inputs: vec![DecoyInput {
+                    public_key: G1Projective::random(&mut rng).to_affine(),
+                    commitment: G1Projective::random(&mut rng).to_affine(),
+                }],
+            }],
+            outputs: vec![Output {
+                public_key: G1Projective::random(&mut rng).to_affine(),
+                amount,
+            }],
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let mut rng = OsRng;
+        let material = test_material(3, &mut rng);
+        let sig = material.sign(b"hello", &mut rng);
+        assert!(verify(b"hello", &sig));
+    }
+
+    #[test]
+    fn verify_rejects_tampered_message() {
+        let mut rng = OsRng;
+        let material = test_material(3, &mut rng);
+        let sig = material.sign(b"hello", &mut rng);
+        assert!(!verify(b"goodbye", &sig));
+    }
+
+    #[test]
+    fn to_bytes_from_bytes_roundtrip() {
+        let mut rng = OsRng;
+        let material = test_material(3, &mut rng);
+        let sig = material.sign(b"hello", &mut rng);
+
+        let decoded = RingCtSignature::from_bytes(&sig.to_bytes()).unwrap();
+        assert!(verify(b"hello", &decoded));
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_input() {
+        let mut rng = OsRng;
+        let material = test_material(3, &mut rng);
+        let sig = material.sign(b"hello", &mut rng);
+
+        let mut bytes = sig.to_bytes();
+        bytes.truncate(bytes.len() - 1);
+        assert!(RingCtSignature::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn from_bytes_rejects_oversized_input_count() {
+        let mut rng = OsRng;
+        let material = test_material(3, &mut rng);
+        let sig = material.sign(b"hello", &mut rng);
+
+        let mut bytes = sig.to_bytes();
+        // Overwrite the leading `mlsags.len()` prefix with a value beyond MAX_INPUTS.
+        bytes[0..8].copy_from_slice(&((MAX_INPUTS as u64) + 1).to_le_bytes());
+        assert!(RingCtSignature::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn verify_batch_agrees_with_verify() {
+        let mut rng = OsRng;
+
+        let good_material = test_material(3, &mut rng);
+        let good_sig = good_material.sign(b"good", &mut rng);
+
+        let bad_material = test_material(5, &mut rng);
+        let mut bad_sig = bad_material.sign(b"bad", &mut rng);
+        // Corrupt one of the output commitments so the balance check fails.
+        bad_sig.output_commitments[0] = G1Projective::random(&mut rng).to_affine();
+
+        let entries: Vec<(&[u8], &RingCtSignature)> =
+            vec![(b"good", &good_sig), (b"bad", &bad_sig)];
+        let failed = verify_batch(&entries);
+
+        assert_eq!(failed, vec![1]);
+        assert!(verify(b"good", &good_sig));
+        assert!(!verify(b"bad", &bad_sig));
+    }
+}